@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::compound_style::CompoundStyle;
+use crate::errors::Result;
+use crate::str_fit::StrFit;
+
+/// How a block of text is aligned within the available width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch every wrapped line but the last one of a paragraph to
+    /// the full width, by distributing the leftover columns across
+    /// the inter-word gaps. Single-word lines and code blocks fall
+    /// back to `Left`.
+    Justified,
+}
+
+/// For a line made of `word_count` words whose natural (unpadded,
+/// single-space-separated) width is `line_width` columns, compute how
+/// many *extra* spaces to insert after each of the `word_count - 1`
+/// inter-word gaps so the line stretches to fill `max_width` columns.
+///
+/// The leftover is handed out left-biased and deterministically: each
+/// gap gets `remaining / gaps` extra spaces, and the `remaining % gaps`
+/// remainder goes one-by-one to the leftmost gaps.
+fn justified_gaps(word_count: usize, line_width: usize, max_width: usize) -> Vec<usize> {
+    let gaps = word_count.saturating_sub(1);
+    if gaps == 0 || line_width >= max_width {
+        return vec![0; gaps];
+    }
+    let remaining = max_width - line_width;
+    let base = remaining / gaps;
+    let extra = remaining % gaps;
+    (0..gaps).map(|i| base + usize::from(i < extra)).collect()
+}
+
+/// Write `words`, normally separated by a single space, stretched to
+/// `max_width` columns (see `justified_gaps`). Widths are counted in
+/// screen columns so wide glyphs don't break the fill.
+///
+/// Used by `Display` impls (e.g. a rendered paragraph line) through a
+/// `fmt::Formatter`; see `queue_justified_words` for the `io::Write`
+/// equivalent used by `CropWriter`.
+pub fn write_justified(
+    f: &mut fmt::Formatter<'_>,
+    style: &CompoundStyle,
+    words: &[&str],
+    max_width: usize,
+) -> fmt::Result {
+    let content_width: usize =
+        words.iter().map(|w| StrFit::width(w)).sum::<usize>() + words.len().saturating_sub(1);
+    let gaps = justified_gaps(words.len(), content_width, max_width);
+    for (i, word) in words.iter().enumerate() {
+        write!(f, "{}", style.apply_to(*word))?;
+        if let Some(&extra) = gaps.get(i) {
+            style.repeat_space(f, 1 + extra)?;
+        }
+    }
+    Ok(())
+}
+
+/// `io::Write` equivalent of `write_justified`, used by `CropWriter` to
+/// stretch a table cell's or a panel's content to fill its column
+/// budget (`Alignment::Justified`).
+pub fn queue_justified_words<W: std::io::Write>(
+    w: &mut W,
+    style: &CompoundStyle,
+    words: &[&str],
+    max_width: usize,
+) -> Result<()> {
+    let content_width: usize =
+        words.iter().map(|w| StrFit::width(w)).sum::<usize>() + words.len().saturating_sub(1);
+    let gaps = justified_gaps(words.len(), content_width, max_width);
+    for (i, word) in words.iter().enumerate() {
+        style.queue_str(w, word)?;
+        if let Some(&extra) = gaps.get(i) {
+            style.queue_str(w, &" ".repeat(1 + extra))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Justified<'a> {
+        style: CompoundStyle,
+        words: Vec<&'a str>,
+        max_width: usize,
+    }
+
+    impl<'a> fmt::Display for Justified<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_justified(f, &self.style, &self.words, self.max_width)
+        }
+    }
+
+    fn justify(words: &[&str], max_width: usize) -> String {
+        Justified {
+            style: CompoundStyle::default(),
+            words: words.to_vec(),
+            max_width,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_even_distribution() {
+        assert_eq!(justify(&["a", "b", "c"], 9), "a   b   c");
+    }
+
+    #[test]
+    fn test_left_biased_remainder() {
+        // natural width "a b c" = 5, remaining = 3 over 2 gaps: 1 extra each + 1 more to the first
+        assert_eq!(justify(&["a", "b", "c"], 8), "a   b  c");
+    }
+
+    #[test]
+    fn test_single_word() {
+        assert_eq!(justify(&["only"], 10), "only");
+    }
+
+    #[test]
+    fn test_queue_justified_words_matches_write_justified() {
+        let style = CompoundStyle::default();
+        let mut buf: Vec<u8> = Vec::new();
+        queue_justified_words(&mut buf, &style, &["a", "b", "c"], 9).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), justify(&["a", "b", "c"], 9));
+    }
+}
@@ -0,0 +1,275 @@
+use crossterm::style::Color;
+
+/// Color support of a terminal, used to downgrade a truecolor (RGB)
+/// color to what the terminal is able to display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+/// levels of the 6 steps of the xterm 256 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Parse a color given as `#rrggbb`, `rrggbb`, `rgb(r, g, b)`, or one
+/// of a handful of common CSS color names, into a `Color::Rgb`.
+///
+/// This lets skins be configured from plain text (e.g. a config file)
+/// instead of requiring `Color` enum literals.
+pub fn parse_color(s: &str) -> Option<Color> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some(inner) = s.strip_prefix("rgb(").and_then(|s| s.strip_suffix(')')) {
+        let mut parts = inner.split(',').map(|p| p.trim().parse::<u8>());
+        return match (parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Some(Color::Rgb { r, g, b }),
+            _ => None,
+        };
+    }
+    if s.len() == 6 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return parse_hex(s);
+    }
+    named_color(s)
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex: String = if hex.len() == 3 {
+        hex.chars().flat_map(|c| [c, c]).collect()
+    } else {
+        hex.to_string()
+    };
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb { r, g, b })
+}
+
+fn named_color(s: &str) -> Option<Color> {
+    let (r, g, b) = match s.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        "orange" => (255, 165, 0),
+        "gray" | "grey" => (128, 128, 128),
+        _ => return None,
+    };
+    Some(Color::Rgb { r, g, b })
+}
+
+/// Downgrade a color to the nearest one representable at `depth`.
+/// Colors which aren't truecolor RGB are returned unchanged.
+pub fn downgrade(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (Color::Rgb { r, g, b }, ColorDepth::Ansi256) => Color::AnsiValue(rgb_to_ansi256(r, g, b)),
+        (Color::Rgb { r, g, b }, ColorDepth::Ansi16) => rgb_to_ansi16(r, g, b),
+        _ => color,
+    }
+}
+
+fn sq_dist(r1: u8, g1: u8, b1: u8, r2: u8, g2: u8, b2: u8) -> u32 {
+    let dr = r1 as i32 - r2 as i32;
+    let dg = g1 as i32 - g2 as i32;
+    let db = b1 as i32 - b2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// index (0..=5) and value of the cube level closest to `v`
+fn nearest_cube_level(v: u8) -> (u8, u8) {
+    let mut best_idx = 0;
+    let mut best_dist = u32::MAX;
+    for (i, &level) in CUBE_LEVELS.iter().enumerate() {
+        let dist = (level as i32 - v as i32).unsigned_abs();
+        if dist < best_dist {
+            best_dist = dist;
+            best_idx = i as u8;
+        }
+    }
+    (best_idx, CUBE_LEVELS[best_idx as usize])
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, rv) = nearest_cube_level(r);
+    let (gi, gv) = nearest_cube_level(g);
+    let (bi, bv) = nearest_cube_level(b);
+    let cube_idx = 16 + 36 * ri + 6 * gi + bi;
+    let cube_dist = sq_dist(r, g, b, rv, gv, bv);
+
+    let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let gray_level = (((luma as i32 - 8) as f32) / 10.0).round().clamp(0.0, 23.0) as u8;
+    let gray_value = 8 + 10 * gray_level;
+    let gray_idx = 232 + gray_level;
+    let gray_dist = sq_dist(r, g, b, gray_value, gray_value, gray_value);
+
+    if gray_dist < cube_dist {
+        gray_idx
+    } else {
+        cube_idx
+    }
+}
+
+fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    let bright = (r as u32 + g as u32 + b as u32) / 3 > 127;
+    let idx = (r > 127) as u8 | (((g > 127) as u8) << 1) | (((b > 127) as u8) << 2);
+    match (idx, bright) {
+        (0, false) => Color::Black,
+        (0, true) => Color::Grey,
+        (1, false) => Color::DarkRed,
+        (1, true) => Color::Red,
+        (2, false) => Color::DarkGreen,
+        (2, true) => Color::Green,
+        (3, false) => Color::DarkYellow,
+        (3, true) => Color::Yellow,
+        (4, false) => Color::DarkBlue,
+        (4, true) => Color::Blue,
+        (5, false) => Color::DarkMagenta,
+        (5, true) => Color::Magenta,
+        (6, false) => Color::DarkCyan,
+        (6, true) => Color::Cyan,
+        (_, false) => Color::Grey,
+        (_, true) => Color::White,
+    }
+}
+
+/// an RGB color as (hue in 0..360, saturation in 0..=1, lightness in 0..=1)
+type Hsl = (f32, f32, f32);
+
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> Hsl {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    if (max - min).abs() < f32::EPSILON {
+        return (0.0, 0.0, l);
+    }
+    let d = max - min;
+    let s = if l > 0.5 {
+        d / (2.0 - max - min)
+    } else {
+        d / (max + min)
+    };
+    let h = if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s, l)
+}
+
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    if s.abs() < f32::EPSILON {
+        let v = (l * 255.0).round() as u8;
+        return (v, v, v);
+    }
+    let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+    let p = 2.0 * l - q;
+    let h = h / 360.0;
+    let to_channel = |t: f32| {
+        let t = t.rem_euclid(1.0);
+        let v = if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 1.0 / 2.0 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        };
+        (v * 255.0).round() as u8
+    };
+    (to_channel(h + 1.0 / 3.0), to_channel(h), to_channel(h - 1.0 / 3.0))
+}
+
+/// lerp hue along the shorter arc of the color circle
+fn lerp_hue(from: f32, to: f32, t: f32) -> f32 {
+    let mut delta = (to - from) % 360.0;
+    if delta > 180.0 {
+        delta -= 360.0;
+    } else if delta < -180.0 {
+        delta += 360.0;
+    }
+    (from + delta * t).rem_euclid(360.0)
+}
+
+/// interpolate between two RGB colors in HSL space, `t` in 0.0..=1.0
+pub fn lerp_rgb(from: (u8, u8, u8), to: (u8, u8, u8), t: f32) -> (u8, u8, u8) {
+    let (fh, fs, fl) = rgb_to_hsl(from.0, from.1, from.2);
+    let (th, ts, tl) = rgb_to_hsl(to.0, to.1, to.2);
+    let h = lerp_hue(fh, th, t);
+    let s = fs + (ts - fs) * t;
+    let l = fl + (tl - fl) * t;
+    hsl_to_rgb(h, s, l)
+}
+
+/// extract the RGB components of a color, when it has any (only
+/// `Color::Rgb` does; other variants return `None`)
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_color("#ff8800"), Some(Color::Rgb { r: 0xff, g: 0x88, b: 0x00 }));
+        assert_eq!(parse_color("ff8800"), Some(Color::Rgb { r: 0xff, g: 0x88, b: 0x00 }));
+    }
+
+    #[test]
+    fn test_parse_rgb_fn() {
+        assert_eq!(parse_color("rgb(255, 136, 0)"), Some(Color::Rgb { r: 255, g: 136, b: 0 }));
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(parse_color("red"), Some(Color::Rgb { r: 255, g: 0, b: 0 }));
+        assert_eq!(parse_color("unknown-color"), None);
+    }
+
+    #[test]
+    fn test_downgrade_ansi256() {
+        assert_eq!(
+            downgrade(Color::Rgb { r: 0, g: 0, b: 0 }, ColorDepth::Ansi256),
+            Color::AnsiValue(16),
+        );
+        assert_eq!(
+            downgrade(Color::Rgb { r: 255, g: 255, b: 255 }, ColorDepth::Ansi256),
+            Color::AnsiValue(231),
+        );
+    }
+
+    #[test]
+    fn test_lerp_rgb_endpoints() {
+        let from = (255, 0, 0);
+        let to = (0, 0, 255);
+        assert_close(lerp_rgb(from, to, 0.0), from);
+        assert_close(lerp_rgb(from, to, 1.0), to);
+    }
+
+    fn assert_close(a: (u8, u8, u8), b: (u8, u8, u8)) {
+        let close = |x: u8, y: u8| (x as i16 - y as i16).abs() <= 1;
+        assert!(
+            close(a.0, b.0) && close(a.1, b.1) && close(a.2, b.2),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+}
@@ -8,7 +8,11 @@ use crossterm::{
     },
 };
 
-use crate::{errors::Result, styled_char::StyledChar};
+use crate::{
+    color::{downgrade, lerp_rgb, parse_color, to_rgb, ColorDepth},
+    errors::Result,
+    styled_char::StyledChar,
+};
 
 /// A style which may be applied to a compound
 #[derive(Default, Clone)]
@@ -74,11 +78,57 @@ impl CompoundStyle {
         cp
     }
 
+    /// Get a new instance of `CompoundStyle` with the foreground color
+    /// parsed from a hex string (`#rrggbb`), a `rgb(r, g, b)` string,
+    /// or a common CSS color name. Returns `None` if `s` isn't valid.
+    pub fn with_fg_hex(s: &str) -> Option<CompoundStyle> {
+        parse_color(s).map(CompoundStyle::with_fg)
+    }
+
     /// Set the foreground color to the passed color.
     pub fn set_fg(&mut self, color: Color) {
         self.object_style.foreground_color = Some(color);
     }
 
+    /// Set the foreground color, parsing it from a hex string
+    /// (`#rrggbb`), a `rgb(r, g, b)` string, or a common CSS color
+    /// name. Returns whether `s` could be parsed.
+    pub fn set_fg_str(&mut self, s: &str) -> bool {
+        match parse_color(s) {
+            Some(color) => {
+                self.set_fg(color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the background color, parsing it the same way as `set_fg_str`.
+    /// Returns whether `s` could be parsed.
+    pub fn set_bg_str(&mut self, s: &str) -> bool {
+        match parse_color(s) {
+            Some(color) => {
+                self.set_bg(color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return a copy of this style with truecolor RGB colors mapped to
+    /// the nearest color representable at `depth`, for terminals
+    /// lacking truecolor support.
+    pub fn downgrade(&self, depth: ColorDepth) -> CompoundStyle {
+        let mut style = self.clone();
+        if let Some(fg) = style.object_style.foreground_color {
+            style.object_style.foreground_color = Some(downgrade(fg, depth));
+        }
+        if let Some(bg) = style.object_style.background_color {
+            style.object_style.background_color = Some(downgrade(bg, depth));
+        }
+        style
+    }
+
     /// Set the background color to the passed color.
     pub fn set_bg(&mut self, color: Color) {
         self.object_style.background_color = Some(color);
@@ -181,4 +231,103 @@ impl CompoundStyle {
     pub fn style_char(&self, nude_char: char) -> StyledChar {
         StyledChar::new(self.clone(), nude_char)
     }
+
+    /// Return a copy of this style with all colors and attributes
+    /// cleared, so that `queue`/`queue_str` emit only the raw text,
+    /// with no SGR/color escape codes.
+    ///
+    /// This is used for the "no-term" rendering mode, for output
+    /// redirected to a file or a non-TTY pipe: width and layout stay
+    /// identical to the styled render, only the escape codes differ.
+    /// See `MadSkin::set_plain_mode` to flip a whole document to this
+    /// mode in one call.
+    pub fn nude(&self) -> CompoundStyle {
+        CompoundStyle::default()
+    }
+
+    /// Build a gradient of `n` styles going from the foreground color
+    /// `from` to `to`, interpolated in HSL space (hue along the
+    /// shorter arc, saturation and lightness linearly), at
+    /// `t = i / (n - 1)` for the i-th style.
+    ///
+    /// Useful for heat-bars, progress fills, or a scrollbar thumb
+    /// whose color varies with position. Returns an empty vec for
+    /// `n == 0`; a single style with the `from` color for `n == 1`.
+    pub fn gradient(from: Color, to: Color, n: usize) -> Vec<CompoundStyle> {
+        if n == 0 {
+            return Vec::new();
+        }
+        if n == 1 {
+            return vec![CompoundStyle::with_fg(from)];
+        }
+        let from_rgb = to_rgb(from).unwrap_or((0, 0, 0));
+        let to_rgb_ = to_rgb(to).unwrap_or((0, 0, 0));
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / (n - 1) as f32;
+                let (r, g, b) = lerp_rgb(from_rgb, to_rgb_, t);
+                CompoundStyle::with_fg(Color::Rgb { r, g, b })
+            })
+            .collect()
+    }
+
+    /// Same as `gradient`, but each color is downgraded to fit `depth`,
+    /// for terminals lacking truecolor support.
+    pub fn gradient_for(from: Color, to: Color, n: usize, depth: ColorDepth) -> Vec<CompoundStyle> {
+        Self::gradient(from, to, n)
+            .into_iter()
+            .map(|style| style.downgrade(depth))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::to_rgb;
+
+    fn assert_close_to(color: Option<Color>, expected: (u8, u8, u8)) {
+        let (r, g, b) = to_rgb(color.expect("color should be set")).expect("should be an rgb color");
+        let close = |a: u8, b: u8| (a as i16 - b as i16).abs() <= 2;
+        assert!(
+            close(r, expected.0) && close(g, expected.1) && close(b, expected.2),
+            "({r}, {g}, {b}) != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_gradient_endpoints() {
+        let from = Color::Rgb { r: 255, g: 0, b: 0 };
+        let to = Color::Rgb { r: 0, g: 0, b: 255 };
+        let styles = CompoundStyle::gradient(from, to, 5);
+        assert_eq!(styles.len(), 5);
+        assert_close_to(styles[0].get_fg(), (255, 0, 0));
+        assert_close_to(styles[4].get_fg(), (0, 0, 255));
+    }
+
+    #[test]
+    fn test_gradient_zero_is_empty() {
+        let from = Color::Rgb { r: 1, g: 2, b: 3 };
+        let to = Color::Rgb { r: 9, g: 8, b: 7 };
+        assert!(CompoundStyle::gradient(from, to, 0).is_empty());
+    }
+
+    #[test]
+    fn test_gradient_single_style() {
+        let from = Color::Rgb { r: 1, g: 2, b: 3 };
+        let to = Color::Rgb { r: 9, g: 8, b: 7 };
+        let styles = CompoundStyle::gradient(from, to, 1);
+        assert_eq!(styles.len(), 1);
+        assert_eq!(styles[0].get_fg(), Some(from));
+    }
+
+    #[test]
+    fn test_gradient_for_downgrades_every_step() {
+        let from = Color::Rgb { r: 255, g: 0, b: 0 };
+        let to = Color::Rgb { r: 0, g: 0, b: 255 };
+        let styles = CompoundStyle::gradient_for(from, to, 3, ColorDepth::Ansi256);
+        for style in &styles {
+            assert!(matches!(style.get_fg(), Some(Color::AnsiValue(_))));
+        }
+    }
 }
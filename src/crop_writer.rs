@@ -0,0 +1,215 @@
+use crate::alignment::queue_justified_words;
+use crate::compound_style::CompoundStyle;
+use crate::errors::Result;
+use crate::str_fit::StrFit;
+use crate::styled_char::StyledChar;
+
+/// A writer wrapping any other one, which guarantees that at most a
+/// fixed number of screen columns are written to it.
+///
+/// This is useful when embedding a termimad-rendered fragment (a
+/// title, a status line, a table cell) into a fixed width area: the
+/// `CropWriter` consumes from an `allowed` column budget and crops
+/// (with a styled ellipsis) whatever doesn't fit, instead of letting
+/// the area overflow.
+pub struct CropWriter<'w, W> {
+    pub writer: &'w mut W,
+    pub allowed: usize, // remaining allowed width, in columns
+    tab_replacement: &'static str,
+}
+
+impl<'w, W> CropWriter<'w, W>
+where
+    W: std::io::Write,
+{
+    pub fn new(writer: &'w mut W, allowed: usize) -> Self {
+        Self {
+            writer,
+            allowed,
+            tab_replacement: "    ",
+        }
+    }
+
+    /// change the string used to replace tabulations (4 spaces by default)
+    pub fn set_tab_replacement(&mut self, tab_replacement: &'static str) {
+        self.tab_replacement = tab_replacement;
+    }
+
+    /// whether the allowed width is already exhausted
+    pub fn is_full(&self) -> bool {
+        self.allowed == 0
+    }
+
+    /// write as much of `s`, styled with `style`, as fits the remaining
+    /// width, expanding tabs first. If some of `s` doesn't fit, it's cut
+    /// and `ellipsis` is written in its place.
+    pub fn queue_str(
+        &mut self,
+        style: &CompoundStyle,
+        s: &str,
+        ellipsis: &StyledChar,
+    ) -> Result<()> {
+        if self.allowed == 0 {
+            return Ok(());
+        }
+        let s = s.replace('\t', self.tab_replacement);
+        let (fitting, cropped) = StrFit::fit(&s, self.allowed);
+        if cropped {
+            let ellipsis_width = ellipsis.char_width();
+            let (fitting, _) = StrFit::fit(&s, self.allowed.saturating_sub(ellipsis_width));
+            self.allowed -= StrFit::width(&fitting);
+            style.queue_str(self.writer, &fitting)?;
+            if self.allowed >= ellipsis_width {
+                ellipsis.queue(self.writer)?;
+                self.allowed -= ellipsis_width;
+            }
+        } else {
+            self.allowed -= StrFit::width(&fitting);
+            style.queue_str(self.writer, &fitting)?;
+        }
+        Ok(())
+    }
+
+    /// write `words` stretched (`Alignment::Justified`) to fill exactly
+    /// the remaining allowed width, consuming the whole budget. Falls
+    /// back to `queue_str` (left-aligned, cropped with `ellipsis` if
+    /// needed) when there's only one word or its natural width already
+    /// reaches or exceeds the budget, matching `Alignment::Justified`'s
+    /// documented fallback to `Left`.
+    pub fn queue_justified(
+        &mut self,
+        style: &CompoundStyle,
+        words: &[&str],
+        ellipsis: &StyledChar,
+    ) -> Result<()> {
+        if self.allowed == 0 {
+            return Ok(());
+        }
+        let gaps = words.len().saturating_sub(1);
+        let content_width: usize = words.iter().map(|w| StrFit::width(w)).sum::<usize>() + gaps;
+        if gaps == 0 || content_width >= self.allowed {
+            return self.queue_str(style, &words.join(" "), ellipsis);
+        }
+        queue_justified_words(self.writer, style, words, self.allowed)?;
+        self.allowed = 0;
+        Ok(())
+    }
+
+    /// right-pad the remaining allowed width with repetitions of `filler`,
+    /// consuming as much of the budget as `filler`'s width evenly covers
+    /// (a leftover narrower than one `filler` cell, e.g. 1 column left
+    /// with a 2-column filler, is left unfilled rather than overflowing)
+    pub fn fill(&mut self, filler: &StyledChar) -> Result<()> {
+        let filler_width = filler.char_width().max(1);
+        let count = self.allowed / filler_width;
+        if count > 0 {
+            filler.queue_repeat(self.writer, count)?;
+            self.allowed -= count * filler_width;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(
+        allowed: usize,
+        s: &str,
+        ellipsis_char: char,
+    ) -> (String, usize) {
+        let style = CompoundStyle::default();
+        let ellipsis = style.style_char(ellipsis_char);
+        let mut buf: Vec<u8> = Vec::new();
+        let remaining = {
+            let mut cw = CropWriter::new(&mut buf, allowed);
+            cw.queue_str(&style, s, &ellipsis).unwrap();
+            cw.allowed
+        };
+        (String::from_utf8(buf).unwrap(), remaining)
+    }
+
+    #[test]
+    fn test_queue_str_fits() {
+        let (out, remaining) = render(10, "abc", '.');
+        assert_eq!(out, "abc");
+        assert_eq!(remaining, 7);
+    }
+
+    #[test]
+    fn test_queue_str_crops_with_narrow_ellipsis() {
+        let (out, remaining) = render(5, "abcdef", '.');
+        assert_eq!(StrFit::width(&out), 5);
+        assert_eq!(out, "abcd.");
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_queue_str_crops_with_wide_ellipsis() {
+        // '国' is a 2-column CJK character
+        let (out, remaining) = render(5, "abcdef", '国');
+        assert_eq!(StrFit::width(&out), 5);
+        assert_eq!(out, "abc国");
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_tab_replacement() {
+        let style = CompoundStyle::default();
+        let ellipsis = style.style_char('.');
+        let mut buf: Vec<u8> = Vec::new();
+        let mut cw = CropWriter::new(&mut buf, 20);
+        cw.set_tab_replacement("  ");
+        cw.queue_str(&style, "a\tb", &ellipsis).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "a  b");
+    }
+
+    #[test]
+    fn test_fill_respects_wide_filler_width() {
+        let style = CompoundStyle::default();
+        let filler = style.style_char('国'); // 2-column filler
+        let mut buf: Vec<u8> = Vec::new();
+        let remaining = {
+            let mut cw = CropWriter::new(&mut buf, 5);
+            cw.fill(&filler).unwrap();
+            cw.allowed
+        };
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(StrFit::width(&out), 4);
+        assert_eq!(remaining, 1); // the odd column can't fit another wide filler
+    }
+
+    #[test]
+    fn test_fill_narrow() {
+        let style = CompoundStyle::default();
+        let filler = style.style_char('-');
+        let mut buf: Vec<u8> = Vec::new();
+        let mut cw = CropWriter::new(&mut buf, 5);
+        cw.fill(&filler).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "-----");
+        assert!(cw.is_full());
+    }
+
+    #[test]
+    fn test_queue_justified_fills_the_budget() {
+        let style = CompoundStyle::default();
+        let ellipsis = style.style_char('.');
+        let mut buf: Vec<u8> = Vec::new();
+        let mut cw = CropWriter::new(&mut buf, 9);
+        cw.queue_justified(&style, &["a", "b", "c"], &ellipsis).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "a   b   c");
+        assert!(cw.is_full());
+    }
+
+    #[test]
+    fn test_queue_justified_falls_back_to_left_when_too_narrow() {
+        let style = CompoundStyle::default();
+        let ellipsis = style.style_char('.');
+        let mut buf: Vec<u8> = Vec::new();
+        let mut cw = CropWriter::new(&mut buf, 3);
+        cw.queue_justified(&style, &["abcdef", "g"], &ellipsis).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "ab.");
+        assert!(cw.is_full());
+    }
+}
@@ -0,0 +1,129 @@
+use crossterm::style::{Attribute, Color};
+
+use crate::alignment::Alignment;
+use crate::compound_style::CompoundStyle;
+use crate::styled_char::StyledChar;
+
+/// The alignment and base style of a block-level element (a table, a
+/// code block...).
+#[derive(Clone, Default)]
+pub struct StyleBlock {
+    pub compound_style: CompoundStyle,
+    pub align: Alignment,
+}
+
+/// The characters used to render a scrollbar.
+#[derive(Clone)]
+pub struct Scrollbar {
+    pub track: StyledChar,
+    pub thumb: StyledChar,
+}
+
+impl Default for Scrollbar {
+    fn default() -> Self {
+        Self {
+            track: StyledChar::new(CompoundStyle::default(), '│'),
+            thumb: StyledChar::new(CompoundStyle::default(), '█'),
+        }
+    }
+}
+
+/// The set of styles used when rendering a markdown document.
+///
+/// Setting `plain_mode` makes `resolve`/`resolve_char` strip colors
+/// and attributes from whatever style they're handed, so an entire
+/// document renders as plain text (no SGR/color escape codes) from a
+/// single `skin.set_plain_mode(true)` call, while width and layout
+/// (wrapping, padding, alignment) stay identical to the styled render.
+/// This is meant for output redirected to a file or a non-TTY pipe.
+#[derive(Clone)]
+pub struct MadSkin {
+    pub bold: CompoundStyle,
+    pub italic: CompoundStyle,
+    pub headers: CompoundStyle,
+    pub table: StyleBlock,
+    pub code_block: StyleBlock,
+    pub scrollbar: Scrollbar,
+    plain_mode: bool,
+}
+
+impl Default for MadSkin {
+    fn default() -> Self {
+        Self {
+            bold: CompoundStyle::with_attr(Attribute::Bold),
+            italic: CompoundStyle::with_attr(Attribute::Italic),
+            headers: CompoundStyle::default(),
+            table: StyleBlock::default(),
+            code_block: StyleBlock::default(),
+            scrollbar: Scrollbar::default(),
+            plain_mode: false,
+        }
+    }
+}
+
+impl MadSkin {
+    /// Set the foreground color used for headers (`# title`, ...).
+    pub fn set_headers_fg(&mut self, color: Color) {
+        self.headers.set_fg(color);
+    }
+
+    /// Switch the whole skin to plain text ("no-term") mode: every
+    /// style `resolve`/`resolve_char` hands out afterwards has its
+    /// colors and attributes stripped, so the whole document renders
+    /// with no SGR/color escape codes, from this one call.
+    pub fn set_plain_mode(&mut self, plain: bool) {
+        self.plain_mode = plain;
+    }
+
+    /// Whether the skin is currently in plain text mode.
+    pub fn plain_mode(&self) -> bool {
+        self.plain_mode
+    }
+
+    /// Resolve a style for rendering: `style` itself, or its `nude()`
+    /// version when plain mode is on. A renderer walking the document
+    /// should always go through this (rather than using `bold`,
+    /// `italic`, ... directly) so that `set_plain_mode` affects the
+    /// whole document at once.
+    pub fn resolve(&self, style: &CompoundStyle) -> CompoundStyle {
+        if self.plain_mode {
+            style.nude()
+        } else {
+            style.clone()
+        }
+    }
+
+    /// Same as `resolve`, for a `StyledChar` (e.g. `scrollbar.thumb`).
+    pub fn resolve_char(&self, styled_char: &StyledChar) -> StyledChar {
+        if self.plain_mode {
+            styled_char.nude()
+        } else {
+            styled_char.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_mode_strips_colors() {
+        let mut skin = MadSkin::default();
+        skin.bold.set_fg(Color::Yellow);
+        assert_eq!(skin.resolve(&skin.bold).get_fg(), Some(Color::Yellow));
+
+        skin.set_plain_mode(true);
+        assert!(skin.plain_mode());
+        assert_eq!(skin.resolve(&skin.bold).get_fg(), None);
+    }
+
+    #[test]
+    fn test_resolve_char_respects_plain_mode() {
+        let mut skin = MadSkin::default();
+        skin.scrollbar.thumb.set_fg(Color::Red);
+        skin.set_plain_mode(true);
+        let thumb = skin.resolve_char(&skin.scrollbar.thumb);
+        assert_eq!(thumb.to_string(), skin.scrollbar.thumb.nude().to_string());
+    }
+}
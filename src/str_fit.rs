@@ -0,0 +1,50 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Helper computing how much of a string fits a given screen width.
+///
+/// Width is always counted in screen columns, not bytes or `char`
+/// count: a CJK character counts for 2 columns, a zero width joiner
+/// for 0, most other characters for 1.
+pub struct StrFit;
+
+impl StrFit {
+    /// the screen width, in columns, of a string
+    pub fn width(s: &str) -> usize {
+        s.chars().map(|c| c.width().unwrap_or(0)).sum()
+    }
+
+    /// the longest prefix of `s` whose width fits in `max_width` columns,
+    /// and whether some of `s` had to be left out
+    pub fn fit(s: &str, max_width: usize) -> (String, bool) {
+        let mut width = 0;
+        let mut fitting = String::new();
+        for c in s.chars() {
+            let cw = c.width().unwrap_or(0);
+            if width + cw > max_width {
+                return (fitting, true);
+            }
+            width += cw;
+            fitting.push(c);
+        }
+        (fitting, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(StrFit::width("abc"), 3);
+        assert_eq!(StrFit::width("好"), 2);
+        assert_eq!(StrFit::width(""), 0);
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(StrFit::fit("abcdef", 3), ("abc".to_string(), true));
+        assert_eq!(StrFit::fit("abc", 10), ("abc".to_string(), false));
+        assert_eq!(StrFit::fit("好好好", 3), ("好".to_string(), true));
+    }
+}
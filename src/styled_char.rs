@@ -4,12 +4,14 @@ use crossterm::{
     queue,
     style::{Color, PrintStyledContent, StyledContent},
 };
+use unicode_width::UnicodeWidthChar;
 
 use crate::compound_style::CompoundStyle;
 use crate::errors::Result;
 
 /// A modifiable character which can be easily written or repeated. Can
 /// be used for bullets, horizontal rules or quote marks.
+#[derive(Clone)]
 pub struct StyledChar {
     compound_style: CompoundStyle,
     nude_char: char,
@@ -67,6 +69,41 @@ impl StyledChar {
     {
         Ok(queue!(w, PrintStyledContent(self.styled_char.clone()))?)
     }
+    /// Return a copy of this styled char with its style stripped, so
+    /// `queue`/`queue_repeat` emit only the raw character(s), with no
+    /// SGR/color escape codes. Used by the "no-term" rendering mode.
+    pub fn nude(&self) -> StyledChar {
+        StyledChar::new(self.compound_style.nude(), self.nude_char)
+    }
+    /// The screen width, in columns, of this char (a CJK char is 2,
+    /// a zero width joiner is 0). Used by callers, like `CropWriter`,
+    /// which must account for real column widths rather than assume
+    /// every char occupies a single column.
+    pub fn char_width(&self) -> usize {
+        self.nude_char.width().unwrap_or(0)
+    }
+    /// Write `count` repetitions of this char, one cell at a time,
+    /// with its color walking a gradient from `from` to `to` (see
+    /// `CompoundStyle::gradient`). Useful for sparklines, heat-bars
+    /// or a scrollbar thumb whose color varies with position.
+    pub fn repeated_gradient<W>(
+        &self,
+        w: &mut W,
+        count: usize,
+        from: Color,
+        to: Color,
+    ) -> Result<()>
+    where
+        W: std::io::Write,
+    {
+        if count == 0 {
+            return Ok(());
+        }
+        for style in CompoundStyle::gradient(from, to, count) {
+            style.queue(w, self.nude_char)?;
+        }
+        Ok(())
+    }
 }
 
 impl Display for StyledChar {
@@ -74,3 +111,51 @@ impl Display for StyledChar {
         self.styled_char.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::str_fit::StrFit;
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(StyledChar::from_fg_char(Color::Red, 'x').char_width(), 1);
+        assert_eq!(StyledChar::from_fg_char(Color::Red, '国').char_width(), 2);
+    }
+
+    #[test]
+    fn test_repeated_gradient_writes_each_cell() {
+        let sc = StyledChar::from_fg_char(Color::Red, '*');
+        let mut buf: Vec<u8> = Vec::new();
+        sc.repeated_gradient(
+            &mut buf,
+            4,
+            Color::Rgb { r: 255, g: 0, b: 0 },
+            Color::Rgb { r: 0, g: 0, b: 255 },
+        )
+        .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out.matches('*').count(), 4);
+        // measure only the visible text, without the SGR escape sequences
+        let visible: String = out
+            .split('\x1b')
+            .enumerate()
+            .map(|(i, part)| if i == 0 { part } else { part.split_once('m').map_or("", |(_, rest)| rest) })
+            .collect();
+        assert_eq!(StrFit::width(&visible), 4);
+    }
+
+    #[test]
+    fn test_repeated_gradient_zero_count_writes_nothing() {
+        let sc = StyledChar::from_fg_char(Color::Red, '*');
+        let mut buf: Vec<u8> = Vec::new();
+        sc.repeated_gradient(
+            &mut buf,
+            0,
+            Color::Rgb { r: 255, g: 0, b: 0 },
+            Color::Rgb { r: 0, g: 0, b: 255 },
+        )
+        .unwrap();
+        assert!(buf.is_empty());
+    }
+}
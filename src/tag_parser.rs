@@ -0,0 +1,157 @@
+use crossterm::style::Attribute;
+
+use crate::compound_style::CompoundStyle;
+
+/// Parse a string containing simple style tags (`<bold>`, `<italic>`,
+/// `<dim>`, `<u>`/`<underline>`, `<strikeout>`, `<fg=...>`, `<bg=...>`)
+/// into a sequence of `(CompoundStyle, String)` spans, each span being
+/// the style in effect for the text that follows it up to the next tag.
+///
+/// Tags nest: opening one pushes a clone of the current style onto a
+/// stack, applies the tag's mutation, and a closing tag (`</tag>` or
+/// the generic `</>`) pops back to the previous style. Unknown tags
+/// (and their matching closing tag) don't touch the style stack at
+/// all and are emitted as literal text instead, exactly as typed.
+///
+/// This lets users build colored status or help lines (e.g.
+/// `<bold>Build</bold> <fg=red>failed</fg>`) without manually
+/// assembling `CompoundStyle` values for every span.
+pub fn parse_tagged_line(s: &str) -> Vec<(CompoundStyle, String)> {
+    let mut spans = Vec::new();
+    let mut stack = vec![CompoundStyle::default()];
+    // one entry per open tag: true for a recognized style tag (which
+    // pushed a style onto `stack`), false for an unknown one emitted as
+    // literal text, so its closing tag is matched the same way
+    let mut tag_stack: Vec<bool> = Vec::new();
+    let mut rest = s;
+
+    while let Some(tag_start) = rest.find('<') {
+        let Some(tag_end) = rest[tag_start..].find('>') else {
+            // no closing `>`: this `<` isn't a tag, so the whole rest,
+            // prefix included, is one literal span pushed after the loop
+            break;
+        };
+        push_span(&mut spans, stack.last().unwrap(), &rest[..tag_start]);
+        let raw_tag = &rest[tag_start..=tag_start + tag_end];
+        let tag = &rest[tag_start + 1..tag_start + tag_end];
+        if tag.starts_with('/') {
+            match tag_stack.pop() {
+                Some(true) => {
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                }
+                Some(false) => push_span(&mut spans, stack.last().unwrap(), raw_tag),
+                None => {} // stray closing tag with nothing open: ignore
+            }
+        } else {
+            let mut style = stack.last().unwrap().clone();
+            if apply_tag(&mut style, tag) {
+                stack.push(style);
+                tag_stack.push(true);
+            } else {
+                push_span(&mut spans, stack.last().unwrap(), raw_tag);
+                tag_stack.push(false);
+            }
+        }
+        rest = &rest[tag_start + tag_end + 1..];
+    }
+    push_span(&mut spans, stack.last().unwrap(), rest);
+    spans
+}
+
+fn push_span(spans: &mut Vec<(CompoundStyle, String)>, style: &CompoundStyle, text: &str) {
+    if !text.is_empty() {
+        spans.push((style.clone(), text.to_string()));
+    }
+}
+
+/// Apply the mutation for `tag` to `style`. Returns whether `tag` was
+/// recognized (including a `fg=`/`bg=` color that parsed correctly);
+/// on `false`, `style` is left unmutated and the caller must emit the
+/// tag as literal text instead.
+fn apply_tag(style: &mut CompoundStyle, tag: &str) -> bool {
+    if let Some(color) = tag.strip_prefix("fg=") {
+        return style.set_fg_str(color);
+    }
+    if let Some(color) = tag.strip_prefix("bg=") {
+        return style.set_bg_str(color);
+    }
+    match tag {
+        "bold" => style.add_attr(Attribute::Bold),
+        "italic" => style.add_attr(Attribute::Italic),
+        "dim" => style.add_attr(Attribute::Dim),
+        "u" | "underline" => style.add_attr(Attribute::Underlined),
+        "strikeout" => style.add_attr(Attribute::CrossedOut),
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_bold() {
+        let spans = parse_tagged_line("<bold>Build</bold> ok");
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].1, "Build");
+        assert_eq!(spans[1].1, " ok");
+    }
+
+    #[test]
+    fn test_generic_close() {
+        let spans = parse_tagged_line("<dim>$</> run");
+        assert_eq!(spans[0].1, "$");
+        assert_eq!(spans[1].1, " run");
+    }
+
+    #[test]
+    fn test_fg_color() {
+        let spans = parse_tagged_line("<fg=red>failed</fg>");
+        assert_eq!(spans[0].1, "failed");
+    }
+
+    #[test]
+    fn test_nesting() {
+        let spans = parse_tagged_line("<bold><fg=red>x</fg></bold>y");
+        assert_eq!(spans[0].1, "x");
+        assert_eq!(spans[1].1, "y");
+        assert!(spans[1].0.get_fg().is_none());
+    }
+
+    #[test]
+    fn test_unknown_tag_is_literal() {
+        let spans = parse_tagged_line("<bogus>text</bogus>");
+        let full: String = spans.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(full, "<bogus>text</bogus>");
+        // and it didn't disturb the style stack: all spans share the default style
+        assert!(spans.iter().all(|(style, _)| style.get_fg().is_none()));
+    }
+
+    #[test]
+    fn test_unknown_tag_nested_in_known_one_keeps_style_stack_consistent() {
+        let spans = parse_tagged_line("<bold><bogus>x</bogus></bold>y");
+        let full: String = spans.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(full, "<bogus>x</bogus>y");
+        // "y" is after </bold> popped back to the default (non-bold) style
+        let (style, text) = spans.last().unwrap();
+        assert_eq!(text, "y");
+        assert!(!style.object_style.attributes.contains(&Attribute::Bold));
+    }
+
+    #[test]
+    fn test_invalid_color_tag_is_literal() {
+        let spans = parse_tagged_line("<fg=not-a-color>x</fg>");
+        let full: String = spans.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(full, "<fg=not-a-color>x</fg>");
+    }
+
+    #[test]
+    fn test_unterminated_tag_does_not_duplicate_prefix() {
+        let spans = parse_tagged_line("plain <bold unterminated no closing bracket");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].1, "plain <bold unterminated no closing bracket");
+    }
+}